@@ -3,7 +3,7 @@ mod utils;
 use utils::*;
 
 use anyhow::bail;
-use image::ImageFormat::Png;
+use image::imageops::FilterType;
 use image::{ColorType, DynamicImage, ImageBuffer, ImageFormat, Rgb};
 use indicatif::ParallelProgressIterator;
 use rayon::prelude::*;
@@ -21,12 +21,173 @@ enum ProcessingMethod {
 	Dependent,
 }
 
+/// A source file paired with the `image` format resolved from its extension,
+/// so decode calls don't have to assume PNG.
+struct SourceImage {
+	path: PathBuf,
+	format: ImageFormat,
+}
+
+/// The format renamed/baked textures (and `combo_0rm`) are written out as.
+#[derive(Clone, Copy, Default)]
+enum OutputFormat {
+	#[default]
+	Png,
+	Webp,
+}
+
+impl OutputFormat {
+	pub fn extension(self) -> &'static str {
+		match self {
+			OutputFormat::Png => "png",
+			OutputFormat::Webp => "webp",
+		}
+	}
+
+	/// Writes `image` to `path`, encoding it according to the selected format.
+	/// WebP is always encoded lossless to preserve the flat-color PBR data.
+	pub fn save(self, image: &DynamicImage, path: impl AsRef<Path>) -> anyhow::Result<()> {
+		match self {
+			OutputFormat::Png => Ok(image.save(path)?),
+
+			OutputFormat::Webp => {
+				//the webp crate only encodes Rgb8/Rgba8 - grayscale, 16-bit (e.g. the Rgb16 normal maps) and float images go through rgba8 first
+				let eight_bit_image;
+
+				let image = match image.color() {
+					ColorType::Rgb8 | ColorType::Rgba8 => image,
+
+					_ => {
+						eight_bit_image = DynamicImage::from(image.to_rgba8());
+
+						&eight_bit_image
+					}
+				};
+
+				let encoder = webp::Encoder::from_image(image).map_err(|error| anyhow::anyhow!("failed to create webp encoder: {error}"))?;
+
+				fs::write(path, &*encoder.encode_lossless())?;
+
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Parsed command-line options for the extraction run.
 #[derive(Default)]
+struct CliOptions {
+	output_format: OutputFormat,
+
+	/// Additional max-size variants (e.g. `[2048, 1024]`) baked into per-resolution subfolders.
+	/// Empty means only the full-resolution output is produced.
+	resolutions: Vec<u32>,
+
+	/// Requested rayon thread count. `None` means use the default global pool.
+	threads: Option<usize>,
+
+	/// Where to write the JSON extraction manifest, if requested.
+	manifest: Option<PathBuf>,
+}
+
+fn parse_cli_options() -> anyhow::Result<CliOptions> {
+	let mut cli_options = CliOptions::default();
+	let mut args = std::env::args().skip(1);
+	let mut threads_given = false;
+
+	while let Some(arg) = args.next() {
+		match arg.as_str() {
+			"--format" => {
+				let value = args.next().ok_or_else(|| anyhow::anyhow!("--format requires a value (png, webp)"))?;
+
+				cli_options.output_format = match value.as_str() {
+					"png" => OutputFormat::Png,
+					"webp" => OutputFormat::Webp,
+					unknown => bail!("unrecognized --format value [{unknown}], expected png or webp"),
+				};
+			}
+
+			"--resolutions" => {
+				let value = args
+					.next()
+					.ok_or_else(|| anyhow::anyhow!("--resolutions requires a comma-separated list, e.g. 4096,2048,1024,512"))?;
+
+				cli_options.resolutions = value
+					.split(',')
+					.map(|part| {
+						let resolution = part.trim().parse::<u32>().map_err(|_| anyhow::anyhow!("invalid resolution value [{part}]"))?;
+
+						if resolution == 0 {
+							bail!("resolution values must be greater than 0, got [{part}]");
+						}
+
+						Ok(resolution)
+					})
+					.collect::<anyhow::Result<Vec<_>>>()?;
+			}
+
+			"--threads" => {
+				let value = args.next().ok_or_else(|| anyhow::anyhow!("--threads requires a value"))?;
+				threads_given = true;
+
+				match value.parse::<usize>() {
+					Ok(count) => cli_options.threads = Some(count),
+					Err(_) => eprintln!("ignoring invalid --threads value [{value}], falling back to the default thread pool"),
+				}
+			}
+
+			"--manifest" => {
+				let value = args.next().ok_or_else(|| anyhow::anyhow!("--manifest requires a path"))?;
+
+				cli_options.manifest = Some(PathBuf::from(value));
+			}
+
+			unknown => bail!("unrecognized argument [{unknown}]"),
+		}
+	}
+
+	//ACGE_THREADS is only consulted when --threads wasn't given explicitly
+	if !threads_given {
+		if let Ok(env_value) = std::env::var("ACGE_THREADS") {
+			match env_value.parse::<usize>() {
+				Ok(count) => cli_options.threads = Some(count),
+				Err(_) => eprintln!("ignoring invalid ACGE_THREADS value [{env_value}]"),
+			}
+		}
+	}
+
+	Ok(cli_options)
+}
+
+/// Installs a custom-sized global rayon pool per `threads`.
+/// `0` or a pool that's already installed falls back to the default pool.
+fn install_thread_pool(threads: Option<usize>) {
+	let Some(threads) = threads else {
+		return;
+	};
+
+	if threads == 0 {
+		eprintln!("--threads/ACGE_THREADS was 0, falling back to the default thread pool");
+
+		return;
+	}
+
+	if let Err(error) = rayon::ThreadPoolBuilder::new().num_threads(threads).build_global() {
+		eprintln!("failed to install a {threads}-thread pool, falling back to the default: {error}");
+	}
+}
+
 struct ImageBake {
 	rename: &'static str,
 	config_lines: Option<SmallVec<[&'static str; 4]>>,
 	color: Option<ColorType>,
 	edited_image: Option<DynamicImage>,
+
+	/// The filter used when deriving downscaled resolution variants from this map.
+	downscale_filter: FilterType,
+
+	/// `NormalDX` ships Y- where we want Y+ (OpenGL); this flips the green channel to match.
+	invert_green: bool,
 }
 
 impl ImageBake {
@@ -36,6 +197,8 @@ impl ImageBake {
 			config_lines: Some(config_multiline.lines().collect()),
 			color: None,
 			edited_image: None,
+			downscale_filter: FilterType::Lanczos3,
+			invert_green: false,
 		}
 	}
 
@@ -45,6 +208,8 @@ impl ImageBake {
 			config_lines: None,
 			color: None,
 			edited_image: None,
+			downscale_filter: FilterType::Lanczos3,
+			invert_green: false,
 		}
 	}
 
@@ -59,6 +224,19 @@ impl ImageBake {
 				config_lines: Some(SmallVec::from_vec(["normal = \"OpenGL\""].to_vec())),
 				color: Some(ColorType::Rgb16),
 				edited_image: None,
+				//a sharpening filter like Lanczos3 can push normal vectors out of renormalization range
+				downscale_filter: FilterType::Triangle,
+				invert_green: false,
+			}),
+
+			//AmbientCG also ships DirectX-convention normals - flip green to line up with our OpenGL output
+			"NormalDX" => ProcessingMethod::Single(ImageBake {
+				rename: "normal",
+				config_lines: Some(SmallVec::from_vec(["normal = \"OpenGL\""].to_vec())),
+				color: Some(ColorType::Rgb16),
+				edited_image: None,
+				downscale_filter: FilterType::Triangle,
+				invert_green: true,
 			}),
 
 			"Metalness" => ProcessingMethod::Dependent,
@@ -69,18 +247,70 @@ impl ImageBake {
 	}
 }
 
-/// Returns `Ok(())` if correct.
-fn correct_extension(path: impl AsRef<Path>) -> Result<(), AcgeError> {
-	match path.as_ref().extension().indoc_str()? {
-		//TODO: more exts
-		//e.g.  | "tga" | "exr"
-		//we will need to carry extension data around though...
-		"png" => Ok(()),
-		extension => Err(AcgeError::InvalidImageFileExtension(extension.into())),
+/// Flips the green channel of a decoded normal map in place, converting the DirectX (Y-)
+/// convention to the OpenGL (Y+) convention our `normal = "OpenGL"` config line expects.
+fn invert_green_channel(image: DynamicImage) -> DynamicImage {
+	match image {
+		DynamicImage::ImageRgb16(mut buffer) => {
+			buffer.par_pixels_mut().for_each(|pixel| pixel.0[1] = u16::MAX - pixel.0[1]);
+
+			DynamicImage::from(buffer)
+		}
+
+		DynamicImage::ImageRgba16(mut buffer) => {
+			buffer.par_pixels_mut().for_each(|pixel| pixel.0[1] = u16::MAX - pixel.0[1]);
+
+			DynamicImage::from(buffer)
+		}
+
+		other => {
+			let mut buffer = other.into_rgb8();
+
+			buffer.par_pixels_mut().for_each(|pixel| pixel.0[1] = u8::MAX - pixel.0[1]);
+
+			DynamicImage::from(buffer)
+		}
 	}
 }
 
+/// A fully baked texture kept in memory so resolution variants can be derived from it
+/// without re-decoding from disk.
+struct BakedOutput {
+	file_name: String,
+	image: DynamicImage,
+	filter: FilterType,
+}
+
+/// Summary of what `process_zip` produced for a single input, used to build the `--manifest` JSON.
+#[derive(Clone, serde::Serialize)]
+struct ProcessReport {
+	folder: String,
+	files: Vec<String>,
+	material_lines: Vec<String>,
+}
+
+/// One entry in the `--manifest` JSON array, pairing an input zip with its outcome.
+#[derive(serde::Serialize)]
+struct ManifestEntry {
+	zip: String,
+	folder: Option<String>,
+	files: Vec<String>,
+	material_lines: Vec<String>,
+	error: Option<String>,
+}
+
+/// Resolves the `image` crate format to decode a file with, based on its extension.
+fn resolve_image_format(path: impl AsRef<Path>) -> Result<ImageFormat, AcgeError> {
+	let extension = path.as_ref().extension().indoc_str()?;
+
+	ImageFormat::from_extension(extension).ok_or_else(|| AcgeError::InvalidImageFileExtension(extension.into()))
+}
+
 fn main() -> anyhow::Result<()> {
+	let cli_options = parse_cli_options()?;
+
+	install_thread_pool(cli_options.threads);
+
 	let cwd = current_dir()?;
 	let mut zip_paths: Vec<PathBuf> = Vec::new();
 
@@ -114,6 +344,12 @@ fn main() -> anyhow::Result<()> {
 
 	zip_paths.sort_unstable();
 
+	//captured before zip_paths is consumed by the parallel extraction below
+	let zip_names: Vec<String> = zip_paths
+		.iter()
+		.map(|zip_path| zip_path.file_name().and_then(|os_str| os_str.to_str()).unwrap_or("<unknown>").to_string())
+		.collect();
+
 	//let the user know what's about to get affected
 	{
 		let mut stdout_lock = stdout().lock();
@@ -140,7 +376,11 @@ fn main() -> anyhow::Result<()> {
 	}
 
 	//extract and collect the extraction results into a vec
-	let results = zip_paths.into_par_iter().progress().map(process_zip).collect::<Vec<_>>();
+	let results = zip_paths
+		.into_par_iter()
+		.progress()
+		.map(|zip_path| process_zip(zip_path, cli_options.output_format, &cli_options.resolutions))
+		.collect::<Vec<_>>();
 
 	//spit out the results
 	{
@@ -150,16 +390,43 @@ fn main() -> anyhow::Result<()> {
 			write!(stdout_lock, "{index}\t")?;
 
 			match result {
-				Ok(()) => stdout_lock.write_all(b"[  OK  ]\n")?,
+				Ok(_) => stdout_lock.write_all(b"[  OK  ]\n")?,
 				Err(error) => writeln!(stdout_lock, "[FAILED]\n\t Error: {error:#?}")?,
 			}
 		}
 	}
 
+	//write the machine-readable manifest, if requested
+	if let Some(manifest_path) = &cli_options.manifest {
+		let manifest_entries: Vec<ManifestEntry> = zip_names
+			.into_iter()
+			.zip(results.iter())
+			.map(|(zip, result)| match result {
+				Ok(report) => ManifestEntry {
+					zip,
+					folder: Some(report.folder.clone()),
+					files: report.files.clone(),
+					material_lines: report.material_lines.clone(),
+					error: None,
+				},
+
+				Err(error) => ManifestEntry {
+					zip,
+					folder: None,
+					files: Vec::new(),
+					material_lines: Vec::new(),
+					error: Some(format!("{error:#}")),
+				},
+			})
+			.collect();
+
+		fs::write(manifest_path, serde_json::to_string_pretty(&manifest_entries)?)?;
+	}
+
 	Ok(())
 }
 
-fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
+fn process_zip(zip_path: PathBuf, output_format: OutputFormat, resolutions: &[u32]) -> anyhow::Result<ProcessReport> {
 	let extract_dir = zip_path.with_extension("");
 	let mut zip_reader = zip::read::ZipArchive::new(BufReader::new(File::open(&zip_path)?))?;
 
@@ -179,7 +446,9 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 	drop(zip_reader);
 
 	let mut to_delete = SmallVec::<[PathBuf; 8]>::new();
-	let mut file_paths = SmallVec::<[PathBuf; 8]>::new();
+	let mut file_paths = SmallVec::<[SourceImage; 8]>::new();
+	let mut baked_outputs = Vec::<BakedOutput>::new();
+	let mut produced_files = Vec::<String>::new();
 	let mut shortest_file_name_index = 0usize;
 	let mut shortest_file_name_len = usize::MAX; //will certainly be lowered with any amount of iteration
 
@@ -204,15 +473,17 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 			bail!("failed to convert file name of path [{file_path:?}] in extract directory");
 		};
 
-		if correct_extension(&file_path).is_ok() {
-			if file_name.len() < shortest_file_name_len {
-				shortest_file_name_index = file_paths.len();
-				shortest_file_name_len = file_name.len();
+		match resolve_image_format(&file_path) {
+			Ok(format) => {
+				if file_name.len() < shortest_file_name_len {
+					shortest_file_name_index = file_paths.len();
+					shortest_file_name_len = file_name.len();
+				}
+
+				file_paths.push(SourceImage { path: file_path, format });
 			}
 
-			file_paths.push(file_path);
-		} else {
-			to_delete.push(file_path);
+			Err(_) => to_delete.push(file_path),
 		}
 	}
 
@@ -222,7 +493,7 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 
 	//the shortest file with the shortest name is the thumbnail
 	//we don't need it
-	to_delete.push(file_paths.remove(shortest_file_name_index));
+	to_delete.push(file_paths.remove(shortest_file_name_index).path);
 
 	//the previous check prevents a crash
 	//this check saves time
@@ -230,10 +501,10 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 		return Err(AcgeError::NoFilesToFilter.into());
 	}
 
-	let mut shortest_common_prefix = file_paths.get(0).ok_or(AcgeError::NoFilesToFilter)?.file_name().indoc_str()?;
+	let mut shortest_common_prefix = file_paths.get(0).ok_or(AcgeError::NoFilesToFilter)?.path.file_name().indoc_str()?;
 
-	for file_path in &file_paths {
-		let file_name = file_path.file_name().indoc_str()?;
+	for source_image in &file_paths {
+		let file_name = source_image.path.file_name().indoc_str()?;
 
 		shortest_common_prefix = shortest_common_prefix.common_prefix(file_name);
 	}
@@ -246,32 +517,66 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 	roughness_file_name.push_str("Roughness");
 
 	//convert roughness -> specular (if it exists)
-	if let Some((index, roughness_file_path)) = file_paths
+	if let Some((index, roughness_image)) = file_paths
 		.iter()
 		.enumerate()
-		.find(|(_, file_path)| file_path.file_name().indoc_str().unwrap() == &roughness_file_name)
+		.find(|(_, source_image)| source_image.path.file_name().indoc_str().unwrap() == &roughness_file_name)
 	{
-		let file_reader = BufReader::new(File::open(roughness_file_path)?);
-		let mut dyn_image = image::load(file_reader, ImageFormat::Png)?;
+		let file_reader = BufReader::new(File::open(&roughness_image.path)?);
+		let mut dyn_image = image::load(file_reader, roughness_image.format)?;
+
+		let specular_file_name = format!("specular.{}", output_format.extension());
 
 		dyn_image.invert();
-		dyn_image.save(roughness_file_path.with_file_name("specular.png"))?;
-		to_delete.push(file_paths.remove(index));
+		output_format.save(&dyn_image, roughness_image.path.with_file_name(&specular_file_name))?;
+		produced_files.push(specular_file_name.clone());
+
+		if !resolutions.is_empty() {
+			baked_outputs.push(BakedOutput {
+				file_name: specular_file_name,
+				image: dyn_image,
+				filter: FilterType::Lanczos3,
+			});
+		}
+
+		to_delete.push(file_paths.remove(index).path);
+	}
+
+	//AmbientCG sometimes ships both normal-map conventions - prefer NormalGL and drop NormalDX
+	let mut normal_gl_file_stem = shortest_common_prefix.clone();
+	normal_gl_file_stem.push_str("NormalGL");
+
+	if file_paths
+		.iter()
+		.any(|source_image| source_image.path.file_stem().indoc_str().unwrap() == &normal_gl_file_stem)
+	{
+		let mut normal_dx_file_stem = shortest_common_prefix.clone();
+		normal_dx_file_stem.push_str("NormalDX");
+
+		if let Some(index) = file_paths
+			.iter()
+			.position(|source_image| source_image.path.file_stem().indoc_str().unwrap() == &normal_dx_file_stem)
+		{
+			to_delete.push(file_paths.remove(index).path);
+		}
 	}
 
 	let mut config_lines = SmallVec::<[&'static str; 8]>::new();
-	let mut multi_process = HashMap::<String, PathBuf>::new();
+	let mut multi_process = HashMap::<String, SourceImage>::new();
 
 	config_lines.push("tile = true");
 
 	//rename remaining files
-	for file_path in file_paths {
+	for SourceImage { path: file_path, format } in file_paths {
 		let postfix = file_path.file_stem().indoc_str()?.split_at(shortest_common_prefix.len()).1;
 
 		if let Some(processing_method) = ImageBake::from_postfix_path(postfix)? {
 			match processing_method {
 				ProcessingMethod::Single(image_bake) => {
-					let new_path = file_path.with_file_name(format!("{}.png", image_bake.rename));
+					let new_file_name = format!("{}.{}", image_bake.rename, output_format.extension());
+					let new_path = file_path.with_file_name(&new_file_name);
+
+					produced_files.push(new_file_name.clone());
 
 					if let Some(mut append_lines) = image_bake.config_lines {
 						config_lines.append(&mut append_lines);
@@ -308,11 +613,14 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 							//change the color to something more common
 							match image.color() {
 								//probably bevy compatible
-								ColorType::L8 | ColorType::La8 | ColorType::Rgb8 | ColorType::Rgba8 | ColorType::Rgb32F | ColorType::Rgba32F => None,
+								ColorType::L8 | ColorType::La8 | ColorType::Rgb8 | ColorType::Rgba8 => None,
 
 								//not bevy compatible (at least not globally, fine for normal maps)
 								ColorType::L16 | ColorType::La16 | ColorType::Rgb16 | ColorType::Rgba16 => Some(image.clone().into_rgba8().into()),
 
+								//EXR/HDR decode to float samples - neither our PNG nor WebP encoder can write those
+								ColorType::Rgb32F | ColorType::Rgba32F => Some(image.clone().into_rgba8().into()),
+
 								color_format => {
 									println!("unrecognized color format {color_format:?}");
 
@@ -327,21 +635,56 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 							edited_image = corrected_image;
 						}
 
-						edited_image.save(file_path.with_file_name(new_path))?;
+						output_format.save(&edited_image, file_path.with_file_name(&new_path))?;
 						to_delete.push(file_path);
+
+						if !resolutions.is_empty() {
+							baked_outputs.push(BakedOutput {
+								file_name: new_file_name,
+								image: edited_image,
+								filter: image_bake.downscale_filter,
+							});
+						}
 					} else {
-						if let Some(corrected_image) = fn_color_space_correction(&image::load(BufReader::new(File::open(&file_path)?), Png)?) {
-							corrected_image.save(file_path.with_file_name(new_path))?;
+						let loaded_image = image::load(BufReader::new(File::open(&file_path)?), format)?;
+						let loaded_image = if image_bake.invert_green { invert_green_channel(loaded_image) } else { loaded_image };
+
+						if let Some(corrected_image) = fn_color_space_correction(&loaded_image) {
+							output_format.save(&corrected_image, file_path.with_file_name(&new_path))?;
 							to_delete.push(file_path);
-						} else {
+
+							if !resolutions.is_empty() {
+								baked_outputs.push(BakedOutput {
+									file_name: new_file_name,
+									image: corrected_image,
+									filter: image_bake.downscale_filter,
+								});
+							}
+						} else if format == ImageFormat::Png
+							&& matches!(output_format, OutputFormat::Png)
+							&& resolutions.is_empty()
+							&& !image_bake.invert_green
+						{
+							//the source bytes are already valid PNG and no re-encode is needed - skip a needless decode+encode round-trip
 							fs::rename(file_path, new_path)?
+						} else {
+							output_format.save(&loaded_image, file_path.with_file_name(&new_path))?;
+							to_delete.push(file_path);
+
+							if !resolutions.is_empty() {
+								baked_outputs.push(BakedOutput {
+									file_name: new_file_name,
+									image: loaded_image,
+									filter: image_bake.downscale_filter,
+								});
+							}
 						}
 					}
 				}
 
 				ProcessingMethod::Dependent => {
 					to_delete.push(file_path.clone());
-					multi_process.insert(postfix.to_string(), file_path);
+					multi_process.insert(postfix.to_string(), SourceImage { path: file_path, format });
 				}
 			}
 		} else {
@@ -359,19 +702,21 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 		//thus we create our own combo zero-roughness-metal texture
 		if let Some(image) = match [multi_process.get("Metalness"), multi_process.get("Roughness")] {
 			//metal material
-			[Some(metalness_path), Some(roughness_path)] => {
+			[Some(metalness_source), Some(roughness_source)] => {
 				config_lines.push("metal = 1.0");
 				config_lines.push("rough = 1.0");
 
-				let metalness_image = image::load(BufReader::new(File::open(metalness_path)?), Png)?.into_luma8();
-				let roughness_image = image::load(BufReader::new(File::open(roughness_path)?), Png)?.into_luma8();
+				let metalness_image = image::load(BufReader::new(File::open(&metalness_source.path)?), metalness_source.format)?.into_luma8();
+				let roughness_image = image::load(BufReader::new(File::open(&roughness_source.path)?), roughness_source.format)?.into_luma8();
 				let [width, height] = [metalness_image.width(), metalness_image.height()];
 
 				if metalness_image.width() != roughness_image.width() || metalness_image.height() != roughness_image.height() {
 					bail!(
-						"bevy metal image requires matching image sizes [{metalness_path:?}] {}x{} [{roughness_path:?}] {}x{}",
+						"bevy metal image requires matching image sizes [{:?}] {}x{} [{:?}] {}x{}",
+						metalness_source.path,
 						width,
 						height,
+						roughness_source.path,
 						roughness_image.width(),
 						roughness_image.height()
 					);
@@ -385,10 +730,10 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 			}
 
 			//rough material
-			[None, Some(roughness_path)] => {
+			[None, Some(roughness_source)] => {
 				config_lines.push("rough = 1.0");
 
-				let mut roughness_image = image::load(BufReader::new(File::open(roughness_path)?), Png)?.into_rgb8();
+				let mut roughness_image = image::load(BufReader::new(File::open(&roughness_source.path)?), roughness_source.format)?.into_rgb8();
 
 				//remove red and blue channel - just green is used
 				//red is unused
@@ -402,10 +747,22 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 			}
 
 			//impossible material?
-			[Some(metalness_path), None] => bail!("Metalness image [{metalness_path:?}] without roughness map."),
+			[Some(metalness_source), None] => bail!("Metalness image [{:?}] without roughness map.", metalness_source.path),
 			_ => None,
 		} {
-			image.save(extract_dir.join("combo_0rm.png"))?;
+			let combo_file_name = format!("combo_0rm.{}", output_format.extension());
+
+			output_format.save(&image, extract_dir.join(&combo_file_name))?;
+			produced_files.push(combo_file_name.clone());
+
+			if !resolutions.is_empty() {
+				baked_outputs.push(BakedOutput {
+					file_name: combo_file_name,
+					image,
+					//packed, non-photometric roughness/metalness channels - a ringing filter can push values out of range the same way it does for normals
+					filter: FilterType::Triangle,
+				});
+			}
 		}
 	}
 
@@ -418,6 +775,27 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 	file_handle.write_all(joined.as_bytes())?;
 	file_handle.write_all(b"\n")?;
 
+	//bake downscaled resolution variants from whatever we kept in memory, one subfolder per max size
+	for &max_size in resolutions {
+		let resolution_dir = extract_dir.join(max_size.to_string());
+
+		fs::create_dir_all(&resolution_dir)?;
+
+		for baked_output in &baked_outputs {
+			let (width, height) = (baked_output.image.width(), baked_output.image.height());
+
+			//preserve aspect ratio and never upscale past the source dimensions
+			let scale = (max_size as f64 / width.max(height) as f64).min(1.0);
+			let resized_width = ((width as f64 * scale).round() as u32).max(1);
+			let resized_height = ((height as f64 * scale).round() as u32).max(1);
+			let resized_image = baked_output.image.resize(resized_width, resized_height, baked_output.filter);
+
+			output_format.save(&resized_image, resolution_dir.join(&baked_output.file_name))?;
+		}
+
+		fs::write(resolution_dir.join("material.toml"), format!("{joined}\n"))?;
+	}
+
 	//batch me glados
 	for file_path in to_delete {
 		fs::remove_file(file_path)?;
@@ -443,7 +821,8 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 	}
 
 	finished_folder = finished_folder.trim_end_matches(['-', '_']);
-	let finished_path = extract_dir.with_file_name(finished_folder.to_ascii_lowercase());
+	let folder_name = finished_folder.to_ascii_lowercase();
+	let finished_path = extract_dir.with_file_name(&folder_name);
 
 	//rename the folder
 	//annoying impl for windows because... windows.
@@ -452,19 +831,12 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 			fs::create_dir(&finished_path)?;
 
 			//move contents of folder to other folder
+			//resolution subfolders move the same way files do - fs::rename works on directories too
 			for entry in read_dir(&extract_dir)? {
 				let Ok(entry) = entry else {
 					continue;
 				};
 
-				let Ok(meta) = entry.metadata() else {
-					continue;
-				};
-
-				if meta.is_dir() {
-					continue;
-				}
-
 				let path = entry.path();
 
 				let Some(file_name) = path.file_name() else {
@@ -483,5 +855,9 @@ fn process_zip(zip_path: PathBuf) -> anyhow::Result<()> {
 		}
 	}
 
-	Ok(())
+	Ok(ProcessReport {
+		folder: folder_name,
+		files: produced_files,
+		material_lines: config_lines.iter().map(|line| line.to_string()).collect(),
+	})
 }